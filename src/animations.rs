@@ -0,0 +1,483 @@
+//! Animations that only need an `embedded_graphics` `DrawTarget` and a [`StatusLed`] to
+//! blink, so they can run unmodified against the real `mipidsi` display or the desktop
+//! `embedded-graphics-simulator` window (see `src/bin/simulator.rs`).
+
+use core::f32::consts::PI;
+use embassy_time::{Duration, Instant, Timer};
+use embedded_graphics::mono_font::{MonoTextStyle, ascii::FONT_10X20};
+use embedded_graphics::pixelcolor::Rgb565;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::{Circle, PrimitiveStyle, Rectangle};
+use embedded_graphics::text::{Alignment, Text};
+// `std`'s own `f32` methods cover this under the `simulator` feature; only the
+// embedded (`no_std`) build needs `micromath`'s extension trait for them.
+#[cfg(not(feature = "simulator"))]
+use micromath::F32Ext;
+
+use crate::image::{STATUS_SPRITE, draw_image};
+use crate::led::StatusLed;
+
+pub struct Rng(u32);
+impl Rng {
+    pub fn new(seed: u32) -> Self {
+        Self(seed)
+    }
+    pub fn next_u32(&mut self) -> u32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 17;
+        self.0 ^= self.0 << 5;
+        self.0
+    }
+}
+
+/// Hann window coefficient for sample `i` of an `n`-sample block: tapers the block
+/// edges to zero so a sharp cutoff doesn't smear spectral leakage across neighboring
+/// FFT bins.
+pub fn hann_window(i: usize, n: usize) -> f32 {
+    0.5 - 0.5 * ((2.0 * PI * i as f32) / (n - 1) as f32).cos()
+}
+
+/// Pixel extent `(x, width)` of bar `bin` of `bars` evenly spanning `total_width`,
+/// distributing the remainder across the bars instead of leaving a gap past the last
+/// one (`total_width / bars` truncates, so a naive fixed-width bar falls short).
+pub fn bar_extent(bin: u32, bars: u32, total_width: u32) -> (u32, u32) {
+    let x0 = bin * total_width / bars;
+    let x1 = (bin + 1) * total_width / bars;
+    (x0, x1 - x0)
+}
+
+/// Maps an FFT bin magnitude to a waterfall color: black at silence, rising through
+/// blue, red and white as it gets louder. Uses the same `ln(magnitude) * 12` curve
+/// `animation_spectrum`'s bar chart maps to bar height, just normalized into a 0..=1
+/// gradient position instead of a pixel height.
+pub fn magnitude_color(magnitude: f32) -> Rgb565 {
+    let t = (magnitude.max(1.0).ln() * 12.0 / 320.0).clamp(0.0, 1.0);
+    if t < 0.33 {
+        let k = t / 0.33;
+        Rgb565::new(0, 0, (k * 31.0).round() as u8)
+    } else if t < 0.66 {
+        let k = (t - 0.33) / 0.33;
+        Rgb565::new((k * 31.0).round() as u8, 0, ((1.0 - k) * 31.0).round() as u8)
+    } else {
+        let k = (t - 0.66) / 0.34;
+        Rgb565::new(31, (k * 63.0).round() as u8, (k * 31.0).round() as u8)
+    }
+}
+
+pub const CIRCLE_COLORS: [Rgb565; 12] = [
+    Rgb565::RED,
+    Rgb565::GREEN,
+    Rgb565::BLUE,
+    Rgb565::BLACK,
+    Rgb565::MAGENTA,
+    Rgb565::CYAN,
+    Rgb565::new(31, 31, 0),
+    Rgb565::new(31, 15, 0),
+    Rgb565::new(15, 31, 0),
+    Rgb565::new(0, 31, 15),
+    Rgb565::new(15, 0, 31),
+    Rgb565::new(31, 0, 15),
+];
+
+pub async fn show_fps<D>(display: &mut D, duration: Duration)
+where
+    D: DrawTarget<Color = Rgb565>,
+{
+    let micros = duration.as_micros();
+    if let Some(fps) = 1_000_000u64.checked_div(micros) {
+        let mut buf = [0u8; 16];
+        let fps_text = {
+            let mut val = fps;
+            let mut i = 0;
+            if val == 0 {
+                buf[0] = b'0';
+                i = 1;
+            } else {
+                let mut temp = [0u8; 10];
+                let mut j = 0;
+                while val > 0 {
+                    temp[j] = (val % 10) as u8 + b'0';
+                    val /= 10;
+                    j += 1;
+                }
+                while j > 0 {
+                    j -= 1;
+                    buf[i] = temp[j];
+                    i += 1;
+                }
+            }
+            core::str::from_utf8(&buf[..i]).unwrap_or("?")
+        };
+
+        let style = MonoTextStyle::new(&FONT_10X20, Rgb565::BLACK);
+        Text::with_alignment(fps_text, Point::new(10, 20), style, Alignment::Left)
+            .draw(display)
+            .ok();
+    }
+}
+
+/// Overlays the compiled-in status sprite in the top-right corner, so there's a small
+/// bit of real bitmap art on screen alongside the programmatic animations.
+pub async fn show_status_sprite<D>(display: &mut D)
+where
+    D: DrawTarget<Color = Rgb565>,
+{
+    let top_right = Point::new(display.bounding_box().size.width as i32 - 8, 0);
+    draw_image(display, top_right, &STATUS_SPRITE).ok();
+}
+
+pub async fn animation_text<D, L>(display: &mut D, led: &mut L, rng: &mut Rng) -> Duration
+where
+    D: DrawTarget<Color = Rgb565>,
+    L: StatusLed,
+{
+    let start = Instant::now();
+    let bounds = display.bounding_box();
+    let text = "-=Ewen=-";
+
+    // Calculate text size dynamically
+    let style_measure = MonoTextStyle::new(&FONT_10X20, Rgb565::BLACK);
+    let text_bbox = Text::new(text, Point::zero(), style_measure).bounding_box();
+    let text_width = text_bbox.size.width as i32;
+    let text_height = text_bbox.size.height as i32;
+
+    let mut pos = Point::new(
+        (rng.next_u32() % (bounds.size.width - text_width as u32)) as i32,
+        (rng.next_u32() % (bounds.size.height - text_height as u32)) as i32 + text_height,
+    );
+    let mut vel = Point::new(2, 2);
+    let mut color_idx = 0;
+
+    // Initial clear
+    display.clear(Rgb565::BLACK).ok();
+
+    for _ in 0..1000 {
+        // 1. Erase previous position using a solid black rectangle
+        let style_erase = MonoTextStyle::new(&FONT_10X20, Rgb565::BLACK);
+        let bbox = Text::new(text, pos, style_erase).bounding_box();
+        Rectangle::new(bbox.top_left, bbox.size)
+            .into_styled(PrimitiveStyle::with_fill(Rgb565::BLACK))
+            .draw(display)
+            .ok();
+
+        // 2. Update position
+        let next_pos = pos + vel;
+        let mut hit = false;
+
+        if next_pos.x <= 0 || next_pos.x + text_width >= bounds.size.width as i32 {
+            vel.x = -vel.x;
+            hit = true;
+        }
+        if next_pos.y <= 0 || next_pos.y + text_height >= bounds.size.height as i32 {
+            vel.y = -vel.y;
+            hit = true;
+        }
+
+        if hit {
+            // Change color and ensure we don't pick Black (index 3) on a Black background
+            color_idx = (color_idx + 1) % CIRCLE_COLORS.len();
+            if CIRCLE_COLORS[color_idx] == Rgb565::BLACK {
+                color_idx = (color_idx + 1) % CIRCLE_COLORS.len();
+            }
+            led.set_high();
+        } else {
+            led.set_low();
+        }
+
+        pos += vel;
+
+        // 3. Draw at new position
+        let style_draw = MonoTextStyle::new(&FONT_10X20, CIRCLE_COLORS[color_idx]);
+        Text::new(text, pos, style_draw).draw(display).ok();
+
+        // Very short delay for smooth movement
+        Timer::after(Duration::from_millis(5)).await;
+    }
+    start.elapsed()
+}
+
+pub async fn animation_circles<D, L>(display: &mut D, led: &mut L) -> Duration
+where
+    D: DrawTarget<Color = Rgb565>,
+    L: StatusLed,
+{
+    let start = Instant::now();
+    display.clear(Rgb565::WHITE).ok();
+    let center = display.bounding_box().center();
+    let radius = 30;
+
+    Circle::new(center - Point::new(radius, radius), (radius * 2) as u32)
+        .into_styled(PrimitiveStyle::with_stroke(Rgb565::BLACK, 2))
+        .draw(display)
+        .ok();
+
+    for i in 0..12 {
+        let angle_deg = (i * 30) as f32;
+        let angle_rad = angle_deg * (PI / 180.0);
+        let x = center.x + (radius as f32 * angle_rad.cos()) as i32;
+        let y = center.y + (radius as f32 * angle_rad.sin()) as i32;
+        let satellite_center = Point::new(x, y);
+        let color = CIRCLE_COLORS[i % CIRCLE_COLORS.len()];
+
+        Circle::new(
+            satellite_center - Point::new(radius, radius),
+            (radius * 2) as u32,
+        )
+        .into_styled(PrimitiveStyle::with_stroke(color, 2))
+        .draw(display)
+        .ok();
+
+        led.set_high();
+        Timer::after(Duration::from_millis(50)).await;
+        led.set_low();
+    }
+    start.elapsed()
+}
+
+pub async fn animation_pixels<D, L>(display: &mut D, led: &mut L, rng: &mut Rng) -> Duration
+where
+    D: DrawTarget<Color = Rgb565>,
+    L: StatusLed,
+{
+    let start = Instant::now();
+    display.clear(Rgb565::WHITE).ok();
+    let size = display.bounding_box().size;
+
+    for _ in 0..(size.width * size.height) / 32 {
+        let r = (rng.next_u32() & 0x1F) as u8;
+        let g = (rng.next_u32() & 0x3F) as u8;
+        let b = (rng.next_u32() & 0x1F) as u8;
+        let color = Rgb565::new(r, g, b);
+
+        let x = ((rng.next_u32() % (size.width / 4)) * 4) as i32;
+        let y = ((rng.next_u32() % (size.height / 4)) * 4) as i32;
+
+        Rectangle::new(Point::new(x, y), Size::new(4, 4))
+            .into_styled(PrimitiveStyle::with_fill(color))
+            .draw(display)
+            .ok();
+
+        if rng.next_u32().is_multiple_of(100) {
+            led.set_high();
+            Timer::after(Duration::from_millis(1)).await;
+            led.set_low();
+        }
+    }
+    start.elapsed()
+}
+
+pub async fn animation_tunnel<D, L>(display: &mut D, led: &mut L) -> Duration
+where
+    D: DrawTarget<Color = Rgb565>,
+    L: StatusLed,
+{
+    let start = Instant::now();
+    let bounds = display.bounding_box();
+    let center = bounds.center();
+    let num_rings = 10;
+    let mut ring_pos = [0f32; 10];
+    for (i, pos) in ring_pos.iter_mut().enumerate() {
+        *pos = i as f32 * 20.0;
+    }
+
+    for _ in 0..300 {
+        display.clear(Rgb565::BLACK).ok();
+
+        for i in 0..num_rings {
+            ring_pos[i] += 4.0; // Increased from 2.0 to 4.0
+            if ring_pos[i] > 200.0 {
+                ring_pos[i] = 0.0;
+            }
+
+            // Using power of 2 for a "depth" effect where circles speed up as they get closer
+            let radius = ((ring_pos[i] * ring_pos[i]) / 120.0) as u32 + 2;
+            let color = CIRCLE_COLORS[i % CIRCLE_COLORS.len()];
+
+            if color == Rgb565::BLACK {
+                continue;
+            }
+
+            Circle::new(
+                center - Point::new(radius as i32, radius as i32),
+                radius * 2,
+            )
+            .into_styled(PrimitiveStyle::with_stroke(color, 2))
+            .draw(display)
+            .ok();
+        }
+
+        led.toggle();
+        // Removed Timer::after delay to run at max SPI speed
+        Timer::after(Duration::from_micros(100)).await;
+    }
+    start.elapsed()
+}
+
+/// A source line as a list of syntax tokens: `(column, color, text)`. `column` is in
+/// character cells, not pixels, so tokens line up on a monospace grid regardless of
+/// how wide the preceding token was.
+pub type CodeLine<'a> = &'a [(i32, Rgb565, &'a str)];
+
+/// Draws one highlighted source line at pixel row `y`, placing each token at
+/// `column * char_width` so per-token colors line up on the `FONT_10X20` grid.
+pub fn draw_highlighted_line<D>(display: &mut D, y: i32, tokens: CodeLine)
+where
+    D: DrawTarget<Color = Rgb565>,
+{
+    let char_width = FONT_10X20.character_size.width as i32;
+    for (column, color, text) in tokens {
+        let style = MonoTextStyle::new(&FONT_10X20, *color);
+        Text::new(text, Point::new(column * char_width, y), style)
+            .draw(display)
+            .ok();
+    }
+}
+
+const CODE_SAMPLE: &[CodeLine] = &[
+    &[
+        (0, Rgb565::MAGENTA, "fn"),
+        (3, Rgb565::CYAN, "animation_tunnel"),
+        (20, Rgb565::WHITE, "<D>("),
+    ],
+    &[
+        (1, Rgb565::WHITE, "display:"),
+        (10, Rgb565::WHITE, "&mut"),
+        (15, Rgb565::CYAN, "D,"),
+    ],
+    &[(0, Rgb565::WHITE, ") {")],
+    &[
+        (1, Rgb565::MAGENTA, "let"),
+        (5, Rgb565::WHITE, "start"),
+        (11, Rgb565::WHITE, "="),
+        (13, Rgb565::CYAN, "Instant::now();"),
+    ],
+    &[
+        (1, Rgb565::MAGENTA, "for"),
+        (5, Rgb565::WHITE, "_"),
+        (7, Rgb565::WHITE, "in"),
+        (10, Rgb565::GREEN, "0..300"),
+        (16, Rgb565::WHITE, "{"),
+    ],
+    &[
+        (2, Rgb565::WHITE, "display.clear(Rgb565::"),
+        (25, Rgb565::YELLOW, "BLACK"),
+        (30, Rgb565::WHITE, ").ok();"),
+    ],
+    &[(1, Rgb565::WHITE, "}")],
+    &[(1, Rgb565::WHITE, "start.elapsed()")],
+    &[(0, Rgb565::WHITE, "}")],
+];
+
+pub async fn animation_code<D, L>(display: &mut D, led: &mut L) -> Duration
+where
+    D: DrawTarget<Color = Rgb565>,
+    L: StatusLed,
+{
+    let start = Instant::now();
+    let bounds = display.bounding_box();
+    let line_height = FONT_10X20.character_size.height as i32;
+    let block_height = CODE_SAMPLE.len() as i32 * line_height;
+
+    let mut scroll = bounds.size.height as i32;
+
+    for _ in 0..600 {
+        display.clear(Rgb565::BLACK).ok();
+
+        for (i, tokens) in CODE_SAMPLE.iter().enumerate() {
+            let y = scroll + i as i32 * line_height;
+            if y > -line_height && y < bounds.size.height as i32 {
+                draw_highlighted_line(display, y, tokens);
+            }
+        }
+
+        scroll -= 1;
+        if scroll + block_height < 0 {
+            scroll = bounds.size.height as i32;
+        }
+
+        led.toggle();
+        Timer::after(Duration::from_millis(20)).await;
+    }
+    start.elapsed()
+}
+
+/// Runs the hardware-independent animations in sequence, showing the FPS readout after
+/// each one. Shared between the embedded `main` and the desktop simulator binary
+/// (`src/bin/simulator.rs`); the touch-paint and audio-spectrum animations stay out of
+/// this driver since they need real touch/ADC peripherals.
+pub async fn run_all_animations<D, L>(display: &mut D, led: &mut L, rng: &mut Rng)
+where
+    D: DrawTarget<Color = Rgb565>,
+    L: StatusLed,
+{
+    let dur = animation_text(display, led, rng).await;
+    show_fps(display, dur).await;
+    show_status_sprite(display).await;
+    Timer::after(Duration::from_secs(1)).await;
+
+    let dur = animation_circles(display, led).await;
+    show_fps(display, dur).await;
+    show_status_sprite(display).await;
+    Timer::after(Duration::from_secs(1)).await;
+
+    let dur = animation_pixels(display, led, rng).await;
+    show_fps(display, dur).await;
+    show_status_sprite(display).await;
+    Timer::after(Duration::from_secs(1)).await;
+
+    let dur = animation_tunnel(display, led).await;
+    show_fps(display, dur).await;
+    show_status_sprite(display).await;
+    Timer::after(Duration::from_secs(1)).await;
+
+    let dur = animation_code(display, led).await;
+    show_fps(display, dur).await;
+    show_status_sprite(display).await;
+    Timer::after(Duration::from_secs(1)).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bar_extent_fills_width_exactly() {
+        // 240 / 64 doesn't divide evenly; the bars must still sum to the full width.
+        let bars = 64;
+        let total_width = 240;
+        let mut covered = 0;
+        for bin in 0..bars {
+            let (x, width) = bar_extent(bin, bars, total_width);
+            assert_eq!(x, covered);
+            covered += width;
+        }
+        assert_eq!(covered, total_width);
+    }
+
+    #[test]
+    fn bar_extent_even_division() {
+        assert_eq!(bar_extent(0, 4, 100), (0, 25));
+        assert_eq!(bar_extent(3, 4, 100), (75, 25));
+    }
+
+    #[test]
+    fn hann_window_tapers_block_edges_to_zero() {
+        assert!(hann_window(0, 128).abs() < 1e-6);
+        assert!((hann_window(127, 128) - 0.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn hann_window_peaks_at_midpoint() {
+        assert!((hann_window(64, 129) - 1.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn magnitude_color_silence_is_black() {
+        assert_eq!(magnitude_color(1.0), Rgb565::BLACK);
+    }
+
+    #[test]
+    fn magnitude_color_loud_is_white() {
+        assert_eq!(magnitude_color(1e15), Rgb565::WHITE);
+    }
+}
@@ -0,0 +1,66 @@
+//! Desktop preview for the `animations` module, so contributors can iterate on the
+//! visuals and check FPS without flashing a Pico. Build with the `simulator` feature,
+//! which switches the library crate to `std` (see `src/lib.rs`).
+#![cfg(feature = "simulator")]
+
+use embassy_pico_mano::animations::{Rng, run_all_animations};
+use embassy_pico_mano::image::{SPLASH_LOGO, draw_image};
+use embassy_pico_mano::led::NullLed;
+use embedded_graphics::pixelcolor::Rgb565;
+use embedded_graphics::prelude::*;
+use embedded_graphics_simulator::{OutputSettingsBuilder, SimulatorDisplay, SimulatorEvent, Window};
+
+/// Wraps the simulator display and window so every batch of drawn pixels is
+/// immediately presented, giving a live preview instead of one static frame per
+/// animation.
+struct LiveDisplay<'a> {
+    display: SimulatorDisplay<Rgb565>,
+    window: &'a mut Window,
+}
+
+impl DrawTarget for LiveDisplay<'_> {
+    type Color = Rgb565;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.display.draw_iter(pixels)?;
+        self.window.update(&self.display);
+        Ok(())
+    }
+}
+
+impl OriginDimensions for LiveDisplay<'_> {
+    fn size(&self) -> Size {
+        self.display.size()
+    }
+}
+
+fn main() {
+    let output_settings = OutputSettingsBuilder::new().scale(2).build();
+    let mut window = Window::new("embassy-pico-mano animations", &output_settings);
+    let mut display = LiveDisplay {
+        display: SimulatorDisplay::new(Size::new(240, 320)),
+        window: &mut window,
+    };
+    let mut led = NullLed;
+    let mut rng = Rng::new(0xACE1);
+
+    display.clear(Rgb565::BLACK).ok();
+    draw_image(&mut display, Point::new(88, 148), &SPLASH_LOGO).ok();
+
+    embassy_futures::block_on(async {
+        loop {
+            run_all_animations(&mut display, &mut led, &mut rng).await;
+            if display
+                .window
+                .events()
+                .any(|event| matches!(event, SimulatorEvent::Quit))
+            {
+                break;
+            }
+        }
+    });
+}
@@ -1,19 +1,18 @@
 #![allow(dead_code)]
-use core::f32::consts::PI;
-use embassy_rp::gpio::Output;
-use embassy_time::{Duration, Instant, Timer};
-use embedded_graphics::mono_font::{MonoTextStyle, ascii::FONT_10X20};
-use embedded_graphics::pixelcolor::Rgb565;
+use embedded_graphics::pixelcolor::{Rgb565, RgbColor};
 use embedded_graphics::prelude::*;
-use embedded_graphics::primitives::{Circle, PrimitiveStyle, Rectangle};
-use embedded_graphics::text::{Alignment, Text};
-use micromath::F32Ext;
+use embedded_graphics::primitives::Rectangle;
 
 pub const WIDTH: usize = 240;
 pub const HEIGHT: usize = 320;
 
 pub struct FrameBuffer {
     pub pixels: &'static mut [Rgb565; WIDTH * HEIGHT],
+    /// Bounding box touched since the last `flush`, grown by `draw_iter`/`clear`.
+    dirty: Option<Rectangle>,
+    /// The region sent to the display on the previous `flush`, so a now-empty patch
+    /// (e.g. something that moved away or got erased) still gets repainted once more.
+    prev_dirty: Option<Rectangle>,
 }
 
 impl DrawTarget for FrameBuffer {
@@ -28,6 +27,7 @@ impl DrawTarget for FrameBuffer {
             if coord.x >= 0 && coord.x < WIDTH as i32 && coord.y >= 0 && coord.y < HEIGHT as i32 {
                 let index = coord.y as usize * WIDTH + coord.x as usize;
                 self.pixels[index] = color;
+                self.mark_dirty(Rectangle::new(coord, Size::new(1, 1)));
             }
         }
         Ok(())
@@ -41,256 +41,180 @@ impl OriginDimensions for FrameBuffer {
 }
 
 impl FrameBuffer {
+    pub fn new(pixels: &'static mut [Rgb565; WIDTH * HEIGHT]) -> Self {
+        Self {
+            pixels,
+            dirty: None,
+            prev_dirty: None,
+        }
+    }
+
     pub fn clear(&mut self, color: Rgb565) {
         self.pixels.fill(color);
+        self.mark_dirty(Rectangle::new(Point::zero(), Size::new(WIDTH as u32, HEIGHT as u32)));
     }
-}
 
-pub struct Rng(pub u32);
-impl Rng {
-    pub fn new(seed: u32) -> Self {
-        Self(seed)
-    }
-    pub fn next(&mut self) -> u32 {
-        self.0 ^= self.0 << 13;
-        self.0 ^= self.0 >> 17;
-        self.0 ^= self.0 << 5;
-        self.0
+    /// Shifts every row down by `rows`, discarding the rows that fall off the bottom
+    /// and leaving the `rows` rows at the top unchanged (the caller fills them in, e.g.
+    /// a waterfall's new top line). Touches the whole buffer, so it's marked dirty in
+    /// full rather than tracked precisely.
+    pub fn scroll_down(&mut self, rows: usize) {
+        let rows = rows.min(HEIGHT);
+        self.pixels.copy_within(0..(HEIGHT - rows) * WIDTH, rows * WIDTH);
+        self.mark_dirty(Rectangle::new(Point::zero(), Size::new(WIDTH as u32, HEIGHT as u32)));
     }
-}
 
-pub async fn animation_text<D>(display: &mut D, led: &mut Output<'_>, rng: &mut Rng) -> Duration
-where
-    D: DrawTarget<Color = Rgb565>,
-{
-    let start = Instant::now();
-    let bounds = display.bounding_box();
-    let text = "-=Ewen=-";
+    fn mark_dirty(&mut self, rect: Rectangle) {
+        self.dirty = Some(match self.dirty {
+            Some(existing) => union_rect(existing, rect),
+            None => rect,
+        });
+    }
 
-    // Calculate text size dynamically
-    let style_measure = MonoTextStyle::new(&FONT_10X20, Rgb565::BLACK);
-    let text_bbox = Text::new(text, Point::zero(), style_measure).bounding_box();
-    let text_width = text_bbox.size.width as i32;
-    let text_height = text_bbox.size.height as i32;
+    /// Sends only the pixels that changed since the last `flush` to the `mipidsi`
+    /// display, as contiguous RGB565 rows over SPI. Repaints the previous flush's
+    /// region too, so content that moved away or got erased is actually cleared on
+    /// screen rather than just in the off-screen buffer.
+    pub fn flush<DI, M, RST>(&mut self, display: &mut mipidsi::Display<DI, M, RST>)
+    where
+        DI: mipidsi::interface::Interface<Word = u8>,
+        M: mipidsi::models::Model<ColorFormat = Rgb565>,
+        RST: embedded_hal::digital::OutputPin,
+    {
+        let this_frame = self.dirty.take();
+        let region = match (this_frame, self.prev_dirty) {
+            (Some(dirty), Some(prev)) => union_rect(dirty, prev),
+            (Some(dirty), None) => dirty,
+            (None, Some(prev)) => prev,
+            (None, None) => return,
+        };
+        let bounds = Rectangle::new(Point::zero(), Size::new(WIDTH as u32, HEIGHT as u32));
+        let region = region.intersection(&bounds);
+        if region.size.width == 0 || region.size.height == 0 {
+            return;
+        }
 
-    let mut pos = Point::new(
-        (rng.next() % (bounds.size.width - text_width as u32)) as i32,
-        (rng.next() % (bounds.size.height - text_height as u32)) as i32 + text_height,
-    );
-    let mut vel = Point::new(2, 2);
-    let mut color_idx = 0;
+        let sx = region.top_left.x as u16;
+        let sy = region.top_left.y as u16;
+        let ex = (region.top_left.x + region.size.width as i32 - 1) as u16;
+        let ey = (region.top_left.y + region.size.height as i32 - 1) as u16;
 
-    // Initial clear
-    display.clear(Rgb565::BLACK).ok();
+        let pixels: &[Rgb565] = &self.pixels[..];
+        let colors =
+            (sy..=ey).flat_map(|y| (sx..=ex).map(move |x| pixels[y as usize * WIDTH + x as usize]));
+        display.set_pixels(sx, sy, ex, ey, colors).ok();
 
-    for _ in 0..1000 {
-        // 1. Erase previous position using a solid black rectangle
-        let style_erase = MonoTextStyle::new(&FONT_10X20, Rgb565::BLACK);
-        let bbox = Text::new(text, pos, style_erase).bounding_box();
-        Rectangle::new(bbox.top_left, bbox.size)
-            .into_styled(PrimitiveStyle::with_fill(Rgb565::BLACK))
-            .draw(display)
-            .ok();
+        // Store only what *this* frame actually touched, not the union we just sent.
+        // A no-draw frame then repaints `prev_dirty` exactly once (clearing stale
+        // content) and goes back to `(None, None)` — a true no-op — on the frame after.
+        self.prev_dirty = this_frame;
+    }
 
-        // 2. Update position
-        let next_pos = pos + vel;
-        let mut hit = false;
+    /// Composites a source RGB565 tile onto the buffer with a per-blit alpha (`0..=255`)
+    /// and an optional transparent color key, blending channel-wise in 5/6/5 space
+    /// instead of overwriting. `src` must hold exactly `size.width * size.height` pixels,
+    /// row-major. Clips to the buffer bounds exactly like `draw_iter`.
+    pub fn blit(&mut self, top_left: Point, size: Size, src: &[Rgb565], alpha: u8, color_key: Option<Rgb565>) {
+        let a = alpha as u32;
+        for row in 0..size.height {
+            let y = top_left.y + row as i32;
+            if y < 0 || y >= HEIGHT as i32 {
+                continue;
+            }
+            for col in 0..size.width {
+                let src_color = src[(row * size.width + col) as usize];
+                if color_key == Some(src_color) {
+                    continue;
+                }
 
-        if next_pos.x <= 0 || next_pos.x + text_width >= bounds.size.width as i32 {
-            vel.x = -vel.x;
-            hit = true;
-        }
-        if next_pos.y <= 0 || next_pos.y + text_height >= bounds.size.height as i32 {
-            vel.y = -vel.y;
-            hit = true;
-        }
+                let x = top_left.x + col as i32;
+                if x < 0 || x >= WIDTH as i32 {
+                    continue;
+                }
 
-        if hit {
-            // Change color and ensure we don't pick Black (index 3) on a Black background
-            color_idx = (color_idx + 1) % CIRCLE_COLORS.len();
-            if CIRCLE_COLORS[color_idx] == Rgb565::BLACK {
-                color_idx = (color_idx + 1) % CIRCLE_COLORS.len();
+                let index = y as usize * WIDTH + x as usize;
+                self.pixels[index] = blend(src_color, self.pixels[index], a);
             }
-            led.set_high();
-        } else {
-            led.set_low();
         }
-
-        pos += vel;
-
-        // 3. Draw at new position
-        let style_draw = MonoTextStyle::new(&FONT_10X20, CIRCLE_COLORS[color_idx]);
-        Text::new(text, pos, style_draw).draw(display).ok();
-
-        // Very short delay for smooth movement
-        Timer::after(Duration::from_millis(5)).await;
+        self.mark_dirty(Rectangle::new(top_left, size));
     }
-    start.elapsed()
 }
 
-pub async fn animation_circles<D>(display: &mut D, led: &mut Output<'_>) -> Duration
-where
-    D: DrawTarget<Color = Rgb565>,
-{
-    let start = Instant::now();
-    display.clear(Rgb565::WHITE).ok();
-    let center = display.bounding_box().center();
-    let radius = 30;
-
-    Circle::new(center - Point::new(radius, radius), (radius * 2) as u32)
-        .into_styled(PrimitiveStyle::with_stroke(Rgb565::BLACK, 2))
-        .draw(display)
-        .ok();
-
-    for i in 0..12 {
-        let angle_deg = (i * 30) as f32;
-        let angle_rad = angle_deg * (PI / 180.0);
-        let x = center.x + (radius as f32 * angle_rad.cos()) as i32;
-        let y = center.y + (radius as f32 * angle_rad.sin()) as i32;
-        let satellite_center = Point::new(x, y);
-        let color = CIRCLE_COLORS[i % CIRCLE_COLORS.len()];
-
-        Circle::new(
-            satellite_center - Point::new(radius, radius),
-            (radius * 2) as u32,
-        )
-        .into_styled(PrimitiveStyle::with_stroke(color, 2))
-        .draw(display)
-        .ok();
-
-        led.set_high();
-        Timer::after(Duration::from_millis(50)).await;
-        led.set_low();
-    }
-    start.elapsed()
+fn blend_channel(src: u8, dst: u8, a: u32) -> u8 {
+    ((src as u32 * a + dst as u32 * (255 - a)) / 255) as u8
 }
 
-pub async fn animation_pixels<D>(display: &mut D, led: &mut Output<'_>, rng: &mut Rng) -> Duration
-where
-    D: DrawTarget<Color = Rgb565>,
-{
-    let start = Instant::now();
-    display.clear(Rgb565::WHITE).ok();
-    let size = display.bounding_box().size;
-
-    for _ in 0..(size.width * size.height) / 32 {
-        let r = (rng.next() & 0x1F) as u8;
-        let g = (rng.next() & 0x3F) as u8;
-        let b = (rng.next() & 0x1F) as u8;
-        let color = Rgb565::new(r, g, b);
-
-        let x = ((rng.next() % (size.width / 4)) * 4) as i32;
-        let y = ((rng.next() % (size.height / 4)) * 4) as i32;
-
-        Rectangle::new(Point::new(x, y), Size::new(4, 4))
-            .into_styled(PrimitiveStyle::with_fill(color))
-            .draw(display)
-            .ok();
+fn blend(src: Rgb565, dst: Rgb565, a: u32) -> Rgb565 {
+    Rgb565::new(
+        blend_channel(src.r(), dst.r(), a),
+        blend_channel(src.g(), dst.g(), a),
+        blend_channel(src.b(), dst.b(), a),
+    )
+}
 
-        if rng.next() % 100 == 0 {
-            led.set_high();
-            Timer::after(Duration::from_millis(1)).await;
-            led.set_low();
-        }
-    }
-    start.elapsed()
+/// Smallest rectangle enclosing both `a` and `b`.
+fn union_rect(a: Rectangle, b: Rectangle) -> Rectangle {
+    let min_x = a.top_left.x.min(b.top_left.x);
+    let min_y = a.top_left.y.min(b.top_left.y);
+    let max_x = (a.top_left.x + a.size.width as i32).max(b.top_left.x + b.size.width as i32);
+    let max_y = (a.top_left.y + a.size.height as i32).max(b.top_left.y + b.size.height as i32);
+    Rectangle::new(
+        Point::new(min_x, min_y),
+        Size::new((max_x - min_x) as u32, (max_y - min_y) as u32),
+    )
 }
 
-pub async fn animation_tunnel<D>(display: &mut D, led: &mut Output<'_>) -> Duration
-where
-    D: DrawTarget<Color = Rgb565>,
-{
-    let start = Instant::now();
-    let bounds = display.bounding_box();
-    let center = bounds.center();
-    let num_rings = 10;
-    let mut ring_pos = [0f32; 10];
-    for i in 0..num_rings {
-        ring_pos[i] = i as f32 * 20.0;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn union_rect_disjoint() {
+        let a = Rectangle::new(Point::new(0, 0), Size::new(10, 10));
+        let b = Rectangle::new(Point::new(50, 60), Size::new(5, 5));
+        let u = union_rect(a, b);
+        assert_eq!(u.top_left, Point::new(0, 0));
+        assert_eq!(u.size, Size::new(55, 65));
     }
 
-    for _ in 0..300 {
-        display.clear(Rgb565::BLACK).ok();
-
-        for i in 0..num_rings {
-            ring_pos[i] += 4.0; // Increased from 2.0 to 4.0
-            if ring_pos[i] > 200.0 {
-                ring_pos[i] = 0.0;
-            }
-
-            // Using power of 2 for a "depth" effect where circles speed up as they get closer
-            let radius = ((ring_pos[i] * ring_pos[i]) / 120.0) as u32 + 2;
-            let color = CIRCLE_COLORS[i % CIRCLE_COLORS.len()];
-
-            if color == Rgb565::BLACK {
-                continue;
-            }
+    #[test]
+    fn union_rect_overlapping() {
+        let a = Rectangle::new(Point::new(10, 10), Size::new(20, 20));
+        let b = Rectangle::new(Point::new(15, 5), Size::new(10, 10));
+        let u = union_rect(a, b);
+        assert_eq!(u.top_left, Point::new(10, 5));
+        assert_eq!(u.size, Size::new(20, 25));
+    }
 
-            Circle::new(
-                center - Point::new(radius as i32, radius as i32),
-                radius * 2,
-            )
-            .into_styled(PrimitiveStyle::with_stroke(color, 2))
-            .draw(display)
-            .ok();
-        }
+    #[test]
+    fn union_rect_one_contains_other() {
+        let outer = Rectangle::new(Point::new(0, 0), Size::new(100, 100));
+        let inner = Rectangle::new(Point::new(10, 10), Size::new(5, 5));
+        assert_eq!(union_rect(outer, inner), outer);
+    }
 
-        led.toggle();
-        // Removed Timer::after delay to run at max SPI speed
-        Timer::after(Duration::from_micros(100)).await;
+    #[test]
+    fn blend_channel_alpha_zero_keeps_dst() {
+        assert_eq!(blend_channel(255, 10, 0), 10);
     }
-    start.elapsed()
-}
 
-pub const CIRCLE_COLORS: [Rgb565; 12] = [
-    Rgb565::RED,
-    Rgb565::GREEN,
-    Rgb565::BLUE,
-    Rgb565::BLACK,
-    Rgb565::MAGENTA,
-    Rgb565::CYAN,
-    Rgb565::new(31, 31, 0),
-    Rgb565::new(31, 15, 0),
-    Rgb565::new(15, 31, 0),
-    Rgb565::new(0, 31, 15),
-    Rgb565::new(15, 0, 31),
-    Rgb565::new(31, 0, 15),
-];
+    #[test]
+    fn blend_channel_alpha_full_takes_src() {
+        assert_eq!(blend_channel(200, 10, 255), 200);
+    }
 
-pub async fn show_fps<D>(display: &mut D, duration: Duration)
-where
-    D: DrawTarget<Color = Rgb565>,
-{
-    let micros = duration.as_micros();
-    if micros > 0 {
-        let fps = 1_000_000 / micros;
-        let mut buf = [0u8; 16];
-        let fps_text = {
-            let mut val = fps;
-            let mut i = 0;
-            if val == 0 {
-                buf[0] = b'0';
-                i = 1;
-            } else {
-                let mut temp = [0u8; 10];
-                let mut j = 0;
-                while val > 0 {
-                    temp[j] = (val % 10) as u8 + b'0';
-                    val /= 10;
-                    j += 1;
-                }
-                while j > 0 {
-                    j -= 1;
-                    buf[i] = temp[j];
-                    i += 1;
-                }
-            }
-            core::str::from_utf8(&buf[..i]).unwrap_or("?")
-        };
+    #[test]
+    fn blend_channel_alpha_mid_averages() {
+        assert_eq!(blend_channel(200, 0, 128), 100);
+    }
 
-        let style = MonoTextStyle::new(&FONT_10X20, Rgb565::BLACK);
-        Text::with_alignment(fps_text, Point::new(10, 20), style, Alignment::Left)
-            .draw(display)
-            .ok();
+    #[test]
+    fn scroll_down_shifts_rows() {
+        let pixels = Box::leak(Box::new([Rgb565::BLACK; WIDTH * HEIGHT]));
+        pixels[WIDTH] = Rgb565::RED; // second row
+        let mut fb = FrameBuffer::new(pixels);
+        fb.scroll_down(1);
+        assert_eq!(fb.pixels[2 * WIDTH], Rgb565::RED);
+        assert_eq!(fb.pixels[WIDTH], Rgb565::BLACK);
     }
 }
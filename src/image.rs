@@ -0,0 +1,83 @@
+//! Compiled-in bitmap assets (`include_bytes!`), for when a splash screen or status
+//! icon needs real pixel art instead of only `embedded_graphics` primitives. Two
+//! formats are supported: raw little-endian RGB565 blobs with a known [`Size`]
+//! ([`ImageAsset::Raw`]), and BMP files decoded and color-converted by `tinybmp`
+//! ([`ImageAsset::Bmp`]). Pairs naturally with [`crate::display::FrameBuffer::blit`]
+//! for assets that need alpha blending or a transparent color key.
+
+use embedded_graphics::image::Image;
+use embedded_graphics::pixelcolor::Rgb565;
+use embedded_graphics::pixelcolor::raw::RawU16;
+use embedded_graphics::prelude::*;
+use tinybmp::Bmp;
+
+/// A compiled-in bitmap, ready to draw onto any `DrawTarget<Color = Rgb565>` with
+/// [`draw_image`].
+pub enum ImageAsset {
+    /// Little-endian RGB565 pixels, row-major, with no header: exactly
+    /// `size.width * size.height` pixels.
+    Raw { data: &'static [u8], size: Size },
+    /// A BMP file, decoded and color-converted to `Rgb565` by `tinybmp`.
+    Bmp(&'static [u8]),
+}
+
+/// The splash/logo frame shown once before the animation loop starts.
+pub const SPLASH_LOGO: ImageAsset = ImageAsset::Raw {
+    data: include_bytes!("../assets/splash_logo.raw"),
+    size: Size::new(64, 24),
+};
+
+/// A small status sprite overlaid in a corner of the display during animations.
+pub const STATUS_SPRITE: ImageAsset = ImageAsset::Bmp(include_bytes!("../assets/status_sprite.bmp"));
+
+/// Decodes a little-endian RGB565 byte blob into its pixels, row-major. Shared between
+/// [`draw_image`]'s `Raw` path and anything — like [`crate::display::FrameBuffer::blit`]
+/// — that needs the colors pre-decoded into a plain slice instead of drawn straight to
+/// a `DrawTarget`.
+pub fn decode_raw(data: &[u8]) -> impl Iterator<Item = Rgb565> + '_ {
+    data.chunks_exact(2)
+        .map(|bytes| Rgb565::from(RawU16::new(u16::from_le_bytes([bytes[0], bytes[1]]))))
+}
+
+/// Draws `asset` onto `target` with its top-left corner at `top_left`.
+pub fn draw_image<D>(target: &mut D, top_left: Point, asset: &ImageAsset) -> Result<(), D::Error>
+where
+    D: DrawTarget<Color = Rgb565>,
+{
+    match asset {
+        ImageAsset::Raw { data, size } => {
+            let pixels = decode_raw(data).enumerate().map(|(i, color)| {
+                let x = i as u32 % size.width;
+                let y = i as u32 / size.width;
+                Pixel(top_left + Point::new(x as i32, y as i32), color)
+            });
+            target.draw_iter(pixels)
+        }
+        ImageAsset::Bmp(bytes) => {
+            let bmp = Bmp::<Rgb565>::from_slice(bytes).expect("invalid compiled-in BMP asset");
+            Image::new(&bmp, top_left).draw(target)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_raw_matches_little_endian_rgb565() {
+        // Rgb565::new(31, 0, 0) == 0xF800, stored little-endian as [0x00, 0xF8].
+        let data = [0x00u8, 0xF8, 0x00, 0x00];
+        let mut pixels = decode_raw(&data);
+        assert_eq!(pixels.next(), Some(Rgb565::new(31, 0, 0)));
+        assert_eq!(pixels.next(), Some(Rgb565::new(0, 0, 0)));
+        assert_eq!(pixels.next(), None);
+    }
+
+    #[test]
+    fn decode_raw_pixel_count_matches_byte_len() {
+        // 2 bytes per Rgb565 pixel.
+        let data = [0u8; 2 * 6];
+        assert_eq!(decode_raw(&data).count(), 6);
+    }
+}
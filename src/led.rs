@@ -0,0 +1,37 @@
+//! A hardware-agnostic status indicator, so the shared animations in [`crate::animations`]
+//! can run against the real RP2040 LED pin or a no-op stub on the desktop simulator.
+
+/// Something the animations can blink to signal activity (collisions, touches, frame
+/// boundaries, ...). Mirrors the subset of `embassy_rp::gpio::Output` the animations use.
+pub trait StatusLed {
+    fn set_high(&mut self);
+    fn set_low(&mut self);
+    fn toggle(&mut self);
+}
+
+#[cfg(not(feature = "simulator"))]
+impl StatusLed for embassy_rp::gpio::Output<'_> {
+    fn set_high(&mut self) {
+        embassy_rp::gpio::Output::set_high(self)
+    }
+
+    fn set_low(&mut self) {
+        embassy_rp::gpio::Output::set_low(self)
+    }
+
+    fn toggle(&mut self) {
+        embassy_rp::gpio::Output::toggle(self)
+    }
+}
+
+/// No-op status LED for hosts without a physical LED, e.g. the desktop simulator.
+#[cfg(feature = "simulator")]
+#[derive(Default)]
+pub struct NullLed;
+
+#[cfg(feature = "simulator")]
+impl StatusLed for NullLed {
+    fn set_high(&mut self) {}
+    fn set_low(&mut self) {}
+    fn toggle(&mut self) {}
+}
@@ -0,0 +1,16 @@
+//! Shared library half of the crate: everything that doesn't need the real RP2040
+//! peripherals lives here, so both `src/main.rs` (the embedded binary) and
+//! `src/bin/simulator.rs` (the desktop preview) can build on top of it.
+//!
+//! `src/main.rs` stays `no_std`/`no_main` and owns the hardware bring-up; the
+//! `simulator` feature switches this crate itself to `std` so it can run on a desktop
+//! alongside `embedded-graphics-simulator`.
+#![cfg_attr(not(feature = "simulator"), no_std)]
+
+pub mod animations;
+pub mod display;
+pub mod image;
+pub mod led;
+
+#[cfg(not(feature = "simulator"))]
+pub mod touch;
@@ -1,267 +1,184 @@
 #![no_std]
 #![no_main]
 
-use core::f32::consts::PI;
 use defmt::*;
 use embassy_executor::Spawner;
-use embassy_rp::gpio::{Level, Output};
+use embassy_pico_mano::animations::{
+    CIRCLE_COLORS, Rng, bar_extent, hann_window, magnitude_color, run_all_animations, show_fps,
+    show_status_sprite,
+};
+use embassy_pico_mano::display::{FrameBuffer, HEIGHT, WIDTH};
+use embassy_pico_mano::image::{ImageAsset, SPLASH_LOGO, decode_raw, draw_image};
+use embassy_pico_mano::touch::TouchController;
+use embassy_rp::adc::{Adc, Channel as AdcChannel, Config as AdcConfig, InterruptHandler as AdcIrqs};
+use embassy_rp::bind_interrupts;
+use embassy_rp::gpio::{Input, Level, Output, Pull};
+use embassy_rp::i2c::{self, I2c};
 use embassy_rp::spi::{Config, Spi};
 use embassy_time::{Delay, Duration, Instant, Timer};
-use embedded_graphics::mono_font::{MonoTextStyle, ascii::FONT_10X20};
 use embedded_graphics::pixelcolor::Rgb565;
 use embedded_graphics::prelude::*;
 use embedded_graphics::primitives::{Circle, PrimitiveStyle, Rectangle};
-use embedded_graphics::text::{Alignment, Text};
 use embedded_hal_bus::spi::ExclusiveDevice;
 use micromath::F32Ext;
+use microfft::Complex32;
 use mipidsi::Builder;
+use static_cell::StaticCell;
 use {defmt_rtt as _, panic_probe as _};
 
-struct Rng(u32);
-impl Rng {
-    fn new(seed: u32) -> Self {
-        Self(seed)
-    }
-    fn next(&mut self) -> u32 {
-        self.0 ^= self.0 << 13;
-        self.0 ^= self.0 >> 17;
-        self.0 ^= self.0 << 5;
-        self.0
-    }
-}
-
-const CIRCLE_COLORS: [Rgb565; 12] = [
-    Rgb565::RED,
-    Rgb565::GREEN,
-    Rgb565::BLUE,
-    Rgb565::BLACK,
-    Rgb565::MAGENTA,
-    Rgb565::CYAN,
-    Rgb565::new(31, 31, 0),
-    Rgb565::new(31, 15, 0),
-    Rgb565::new(15, 31, 0),
-    Rgb565::new(0, 31, 15),
-    Rgb565::new(15, 0, 31),
-    Rgb565::new(31, 0, 15),
-];
-
-async fn show_fps<D>(display: &mut D, duration: Duration)
-where
-    D: DrawTarget<Color = Rgb565>,
-{
-    let micros = duration.as_micros();
-    if micros > 0 {
-        let fps = 1_000_000 / micros;
-        let mut buf = [0u8; 16];
-        let fps_text = {
-            let mut val = fps;
-            let mut i = 0;
-            if val == 0 {
-                buf[0] = b'0';
-                i = 1;
-            } else {
-                let mut temp = [0u8; 10];
-                let mut j = 0;
-                while val > 0 {
-                    temp[j] = (val % 10) as u8 + b'0';
-                    val /= 10;
-                    j += 1;
-                }
-                while j > 0 {
-                    j -= 1;
-                    buf[i] = temp[j];
-                    i += 1;
-                }
-            }
-            core::str::from_utf8(&buf[..i]).unwrap_or("?")
-        };
-
-        let style = MonoTextStyle::new(&FONT_10X20, Rgb565::BLACK);
-        Text::with_alignment(fps_text, Point::new(10, 20), style, Alignment::Left)
-            .draw(display)
-            .ok();
-    }
-}
-
-async fn animation_text<D>(display: &mut D, led: &mut Output<'_>, rng: &mut Rng) -> Duration
+bind_interrupts!(struct Irqs {
+    ADC_IRQ_FIFO => AdcIrqs;
+});
+
+/// FFT input length. Must be a power of two for `microfft::complex::cfft_128`; only
+/// the first N/2 bins carry usable (non-mirrored) spectral information.
+const SPECTRUM_SAMPLES: usize = 128;
+const SPECTRUM_BARS: usize = 64;
+
+/// Must match `image::SPLASH_LOGO`'s `size` so the decoded buffer below is exactly
+/// one logo's worth of pixels.
+const LOGO_PIXEL_COUNT: usize = 64 * 24;
+
+async fn animation_spectrum<D>(
+    display: &mut D,
+    led: &mut Output<'_>,
+    adc: &mut Adc<'_, embassy_rp::adc::Async>,
+    adc_channel: &mut AdcChannel<'_>,
+) -> Duration
 where
     D: DrawTarget<Color = Rgb565>,
 {
     let start = Instant::now();
-    let bounds = display.bounding_box();
-    let text = "-=Ewen=-";
-
-    // Calculate text size dynamically
-    let style_measure = MonoTextStyle::new(&FONT_10X20, Rgb565::BLACK);
-    let text_bbox = Text::new(text, Point::zero(), style_measure).bounding_box();
-    let text_width = text_bbox.size.width as i32;
-    let text_height = text_bbox.size.height as i32;
-
-    let mut pos = Point::new(
-        (rng.next() % (bounds.size.width - text_width as u32)) as i32,
-        (rng.next() % (bounds.size.height - text_height as u32)) as i32 + text_height,
-    );
-    let mut vel = Point::new(2, 2);
-    let mut color_idx = 0;
-
-    // Initial clear
     display.clear(Rgb565::BLACK).ok();
+    let bounds = display.bounding_box();
 
-    for _ in 0..1000 {
-        // 1. Erase previous position using a solid black rectangle
-        let style_erase = MonoTextStyle::new(&FONT_10X20, Rgb565::BLACK);
-        let bbox = Text::new(text, pos, style_erase).bounding_box();
-        Rectangle::new(bbox.top_left, bbox.size)
-            .into_styled(PrimitiveStyle::with_fill(Rgb565::BLACK))
-            .draw(display)
-            .ok();
-
-        // 2. Update position
-        let next_pos = pos + vel;
-        let mut hit = false;
-
-        if next_pos.x <= 0 || next_pos.x + text_width >= bounds.size.width as i32 {
-            vel.x = -vel.x;
-            hit = true;
-        }
-        if next_pos.y <= 0 || next_pos.y + text_height >= bounds.size.height as i32 {
-            vel.y = -vel.y;
-            hit = true;
-        }
-
-        if hit {
-            // Change color and ensure we don't pick Black (index 3) on a Black background
-            color_idx = (color_idx + 1) % CIRCLE_COLORS.len();
-            if CIRCLE_COLORS[color_idx] == Rgb565::BLACK {
-                color_idx = (color_idx + 1) % CIRCLE_COLORS.len();
-            }
-            led.set_high();
-        } else {
-            led.set_low();
+    for _ in 0..200 {
+        let mut buf = [Complex32::new(0.0, 0.0); SPECTRUM_SAMPLES];
+        for (i, slot) in buf.iter_mut().enumerate() {
+            let sample = adc.read(adc_channel).await.unwrap_or(0) as f32;
+            let window = hann_window(i, SPECTRUM_SAMPLES);
+            *slot = Complex32::new((sample - 2048.0) * window, 0.0);
         }
 
-        pos += vel;
+        microfft::complex::cfft_128(&mut buf);
 
-        // 3. Draw at new position
-        let style_draw = MonoTextStyle::new(&FONT_10X20, CIRCLE_COLORS[color_idx]);
-        Text::new(text, pos, style_draw).draw(display).ok();
-
-        // Very short delay for smooth movement
-        Timer::after(Duration::from_millis(5)).await;
-    }
-    start.elapsed()
-}
-
-async fn animation_circles<D>(display: &mut D, led: &mut Output<'_>) -> Duration
-where
-    D: DrawTarget<Color = Rgb565>,
-{
-    let start = Instant::now();
-    display.clear(Rgb565::WHITE).ok();
-    let center = display.bounding_box().center();
-    let radius = 30;
-
-    Circle::new(center - Point::new(radius, radius), (radius * 2) as u32)
-        .into_styled(PrimitiveStyle::with_stroke(Rgb565::BLACK, 2))
-        .draw(display)
-        .ok();
-
-    for i in 0..12 {
-        let angle_deg = (i * 30) as f32;
-        let angle_rad = angle_deg * (PI / 180.0);
-        let x = center.x + (radius as f32 * angle_rad.cos()) as i32;
-        let y = center.y + (radius as f32 * angle_rad.sin()) as i32;
-        let satellite_center = Point::new(x, y);
-        let color = CIRCLE_COLORS[i % CIRCLE_COLORS.len()];
-
-        Circle::new(
-            satellite_center - Point::new(radius, radius),
-            (radius * 2) as u32,
-        )
-        .into_styled(PrimitiveStyle::with_stroke(color, 2))
-        .draw(display)
-        .ok();
+        display.clear(Rgb565::BLACK).ok();
+        for bin in 1..=SPECTRUM_BARS {
+            let c = buf[bin];
+            let magnitude = (c.re * c.re + c.im * c.im).sqrt();
+            let height = (magnitude.max(1.0).ln() * 12.0) as u32;
+            let height = height.min(bounds.size.height);
+            let color = CIRCLE_COLORS[bin % CIRCLE_COLORS.len()];
+
+            let (x, bar_width) = bar_extent((bin - 1) as u32, SPECTRUM_BARS as u32, bounds.size.width);
+            let y = bounds.size.height - height;
+            Rectangle::new(Point::new(x as i32, y as i32), Size::new(bar_width, height))
+                .into_styled(PrimitiveStyle::with_fill(color))
+                .draw(display)
+                .ok();
+        }
 
-        led.set_high();
-        Timer::after(Duration::from_millis(50)).await;
-        led.set_low();
+        led.toggle();
+        Timer::after(Duration::from_millis(20)).await;
     }
     start.elapsed()
 }
 
-async fn animation_pixels<D>(display: &mut D, led: &mut Output<'_>, rng: &mut Rng) -> Duration
+/// Waterfall variant of [`animation_spectrum`]: same ADC-sampled FFT each frame, but
+/// instead of redrawing a bar chart, scrolls `fb` down one row and paints the new
+/// spectrum as a color-mapped top row, so loudness history scrolls down the screen
+/// over time like a spectrogram.
+async fn animation_spectrum_waterfall<DI, M, RST>(
+    display: &mut mipidsi::Display<DI, M, RST>,
+    fb: &mut FrameBuffer,
+    led: &mut Output<'_>,
+    adc: &mut Adc<'_, embassy_rp::adc::Async>,
+    adc_channel: &mut AdcChannel<'_>,
+) -> Duration
 where
-    D: DrawTarget<Color = Rgb565>,
+    DI: mipidsi::interface::Interface<Word = u8>,
+    M: mipidsi::models::Model<ColorFormat = Rgb565>,
+    RST: embedded_hal::digital::OutputPin,
 {
     let start = Instant::now();
-    display.clear(Rgb565::WHITE).ok();
-    let size = display.bounding_box().size;
-
-    for _ in 0..(size.width * size.height) / 32 {
-        let r = (rng.next() & 0x1F) as u8;
-        let g = (rng.next() & 0x3F) as u8;
-        let b = (rng.next() & 0x1F) as u8;
-        let color = Rgb565::new(r, g, b);
+    fb.clear(Rgb565::BLACK);
+    fb.flush(display);
+    let width = fb.bounding_box().size.width;
+
+    for _ in 0..200 {
+        let mut buf = [Complex32::new(0.0, 0.0); SPECTRUM_SAMPLES];
+        for (i, slot) in buf.iter_mut().enumerate() {
+            let sample = adc.read(adc_channel).await.unwrap_or(0) as f32;
+            let window = hann_window(i, SPECTRUM_SAMPLES);
+            *slot = Complex32::new((sample - 2048.0) * window, 0.0);
+        }
 
-        let x = ((rng.next() % (size.width / 4)) * 4) as i32;
-        let y = ((rng.next() % (size.height / 4)) * 4) as i32;
+        microfft::complex::cfft_128(&mut buf);
 
-        Rectangle::new(Point::new(x, y), Size::new(4, 4))
-            .into_styled(PrimitiveStyle::with_fill(color))
-            .draw(display)
-            .ok();
+        fb.scroll_down(1);
+        for bin in 1..=SPECTRUM_BARS {
+            let c = buf[bin];
+            let magnitude = (c.re * c.re + c.im * c.im).sqrt();
+            let color = magnitude_color(magnitude);
 
-        if rng.next() % 100 == 0 {
-            led.set_high();
-            Timer::after(Duration::from_millis(1)).await;
-            led.set_low();
+            let (x, bar_width) = bar_extent((bin - 1) as u32, SPECTRUM_BARS as u32, width);
+            Rectangle::new(Point::new(x as i32, 0), Size::new(bar_width, 1))
+                .into_styled(PrimitiveStyle::with_fill(color))
+                .draw(fb)
+                .ok();
         }
+
+        fb.flush(display);
+        led.toggle();
+        Timer::after(Duration::from_millis(20)).await;
     }
     start.elapsed()
 }
 
-async fn animation_tunnel<D>(display: &mut D, led: &mut Output<'_>) -> Duration
+/// Draws into `fb` and flushes only the changed region to `display` each frame. Most
+/// of the canvas is untouched between strokes, so the dirty-rect flush sends a handful
+/// of brush-sized SPI writes per frame instead of a full 240x320 frame every time.
+async fn animation_paint<DI, M, RST>(
+    display: &mut mipidsi::Display<DI, M, RST>,
+    fb: &mut FrameBuffer,
+    logo: &[Rgb565],
+    led: &mut Output<'_>,
+    touch: &mut TouchController<'_>,
+) -> Duration
 where
-    D: DrawTarget<Color = Rgb565>,
+    DI: mipidsi::interface::Interface<Word = u8>,
+    M: mipidsi::models::Model<ColorFormat = Rgb565>,
+    RST: embedded_hal::digital::OutputPin,
 {
     let start = Instant::now();
-    let bounds = display.bounding_box();
-    let center = bounds.center();
-    let num_rings = 10;
-    let mut ring_pos = [0f32; 10];
-    for i in 0..num_rings {
-        ring_pos[i] = i as f32 * 20.0;
-    }
-
-    for _ in 0..300 {
-        display.clear(Rgb565::BLACK).ok();
-
-        for i in 0..num_rings {
-            ring_pos[i] += 4.0; // Increased from 2.0 to 4.0
-            if ring_pos[i] > 200.0 {
-                ring_pos[i] = 0.0;
-            }
-
-            // Using power of 2 for a "depth" effect where circles speed up as they get closer
-            let radius = ((ring_pos[i] * ring_pos[i]) / 120.0) as u32 + 2;
-            let color = CIRCLE_COLORS[i % CIRCLE_COLORS.len()];
+    fb.clear(Rgb565::BLACK);
+    // A translucent watermark in the corner, alpha-composited with FrameBuffer::blit
+    // instead of drawn opaquely, so brush strokes still show through it.
+    fb.blit(Point::new(4, 4), Size::new(64, 24), logo, 96, Some(Rgb565::BLACK));
+    fb.flush(display);
+    let brush_radius = 4u32;
+    let mut color_idx = 0;
 
-            if color == Rgb565::BLACK {
-                continue;
+    for _ in 0..2000 {
+        if let Some(point) = touch.read_point().await {
+            let color = CIRCLE_COLORS[color_idx % CIRCLE_COLORS.len()];
+            if color != Rgb565::BLACK {
+                Circle::new(
+                    point - Point::new(brush_radius as i32, brush_radius as i32),
+                    brush_radius * 2,
+                )
+                .into_styled(PrimitiveStyle::with_fill(color))
+                .draw(fb)
+                .ok();
             }
-
-            Circle::new(
-                center - Point::new(radius as i32, radius as i32),
-                radius * 2,
-            )
-            .into_styled(PrimitiveStyle::with_stroke(color, 2))
-            .draw(display)
-            .ok();
+            color_idx = (color_idx + 1) % CIRCLE_COLORS.len();
+            led.set_high();
+        } else {
+            led.set_low();
         }
 
-        led.toggle();
-        // Removed Timer::after delay to run at max SPI speed
-        Timer::after(Duration::from_micros(100)).await;
+        fb.flush(display);
+        Timer::after(Duration::from_millis(10)).await;
     }
     start.elapsed()
 }
@@ -309,28 +226,60 @@ async fn main(_spawner: Spawner) {
 
     info!("Display initialized!");
 
+    // I2C1 configuration for the FT6x06 touch controller
+    // SCL = GP7, SDA = GP6, IRQ = GP22 (active low while a contact is down)
+    let i2c = I2c::new_blocking(p.I2C1, p.PIN_7, p.PIN_6, i2c::Config::default());
+    let touch_irq = Input::new(p.PIN_22, Pull::Up);
+    let mut touch = TouchController::new(i2c, touch_irq);
+
+    // ADC0 = GP26, sampling a line-level audio input for the spectrum animation
+    let mut adc = Adc::new(p.ADC, Irqs, AdcConfig::default());
+    let mut adc_channel = AdcChannel::new_pin(p.PIN_26, Pull::None);
+
     let mut led = Output::new(p.PIN_25, Level::Low);
     let mut rng = Rng::new(0xACE1);
 
+    static FB_PIXELS: StaticCell<[Rgb565; WIDTH * HEIGHT]> = StaticCell::new();
+    let fb_pixels = FB_PIXELS.init([Rgb565::BLACK; WIDTH * HEIGHT]);
+    let mut fb = FrameBuffer::new(fb_pixels);
+
+    // Decoded once so the paint animation's blit watermark doesn't re-decode the
+    // asset's bytes on every frame.
+    static LOGO_PIXELS: StaticCell<[Rgb565; LOGO_PIXEL_COUNT]> = StaticCell::new();
+    let logo_pixels = LOGO_PIXELS.init([Rgb565::BLACK; LOGO_PIXEL_COUNT]);
+    if let ImageAsset::Raw { data, .. } = &SPLASH_LOGO {
+        for (px, color) in logo_pixels.iter_mut().zip(decode_raw(data)) {
+            *px = color;
+        }
+    }
+
+    info!("Splash");
+    display.clear(Rgb565::BLACK).ok();
+    draw_image(&mut display, Point::new(88, 148), &SPLASH_LOGO).ok();
+    Timer::after(Duration::from_secs(1)).await;
+
     loop {
-        info!("Animation 1: Bouncing Text");
-        let dur = animation_text(&mut display, &mut led, &mut rng).await;
-        show_fps(&mut display, dur).await;
-        Timer::after(Duration::from_secs(1)).await;
+        info!("Animations 1-5: shared demo reel");
+        run_all_animations(&mut display, &mut led, &mut rng).await;
 
-        info!("Animation 2: Circles");
-        let dur = animation_circles(&mut display, &mut led).await;
+        info!("Animation 6: Audio Spectrum");
+        let dur = animation_spectrum(&mut display, &mut led, &mut adc, &mut adc_channel).await;
         show_fps(&mut display, dur).await;
+        show_status_sprite(&mut display).await;
         Timer::after(Duration::from_secs(1)).await;
 
-        info!("Animation 3: Pixels");
-        let dur = animation_pixels(&mut display, &mut led, &mut rng).await;
+        info!("Animation 6b: Audio Spectrum Waterfall");
+        let dur =
+            animation_spectrum_waterfall(&mut display, &mut fb, &mut led, &mut adc, &mut adc_channel)
+                .await;
         show_fps(&mut display, dur).await;
+        show_status_sprite(&mut display).await;
         Timer::after(Duration::from_secs(1)).await;
 
-        info!("Animation 4: Tunnel");
-        let dur = animation_tunnel(&mut display, &mut led).await;
+        info!("Animation 7: Touch Paint");
+        let dur = animation_paint(&mut display, &mut fb, logo_pixels, &mut led, &mut touch).await;
         show_fps(&mut display, dur).await;
+        show_status_sprite(&mut display).await;
         Timer::after(Duration::from_secs(1)).await;
     }
 }
@@ -1,11 +1,26 @@
 use embassy_rp::gpio::Input;
 use embassy_rp::i2c::{Blocking, I2c};
 use embassy_rp::peripherals::I2C1;
+use embedded_graphics::prelude::Point;
 use ft6x06_rs::FT6x06;
 
+/// Panel resolution as configured in `main` (`display_size(240, 320)`).
+const PANEL_WIDTH: i32 = 240;
+const PANEL_HEIGHT: i32 = 320;
+
+/// Raw FT6x06 coordinate range measured against this panel. The controller reports
+/// touches on its own sensor-glass grid (12-bit ADC, 0..=4095 nominal range per the
+/// FT6x06 datasheet), not 0..=239 / 0..=319, and the glass is never tapped flush to
+/// its electrical edges, so raw samples are rescaled into the panel range before
+/// anything else. Measured by logging raw (x, y) while tapping all four corners of
+/// this panel; re-measure against your unit if touches land off from where you tap.
+const RAW_X_MIN: i32 = 34;
+const RAW_X_MAX: i32 = 4062;
+const RAW_Y_MIN: i32 = 28;
+const RAW_Y_MAX: i32 = 4071;
+
 pub struct TouchController<'a> {
     pub touch: FT6x06<I2c<'a, I2C1, Blocking>>,
-    #[allow(dead_code)]
     pub irq: Input<'a>,
 }
 
@@ -17,8 +32,31 @@ impl<'a> TouchController<'a> {
         }
     }
 
-    #[allow(dead_code)]
     pub fn is_touched(&self) -> bool {
         self.irq.is_low()
     }
+
+    /// Reads the current touch contact, if any, and maps it into the `embedded_graphics`
+    /// `Point` space `main` draws into. The FT6x06 reports raw sensor-glass coordinates,
+    /// rescaled here into the panel range; `main` also sets up the ILI9341 with
+    /// `Rotation::Deg0` plus `flip_horizontal`, so the panel's X axis runs opposite to
+    /// the display's and needs mirroring here to line up.
+    pub async fn read_point(&mut self) -> Option<Point> {
+        if !self.is_touched() {
+            return None;
+        }
+
+        let event = self.touch.get_touch_event().ok().flatten()?;
+        let (raw_x, raw_y) = (event.primary_point.x, event.primary_point.y);
+
+        // Rescale from the controller's raw range into the panel's pixel range before
+        // mirroring/clamping, so a unit whose raw range isn't 0..240 / 0..320 still
+        // lands under the finger.
+        let screen_x = (raw_x as i32 - RAW_X_MIN) * (PANEL_WIDTH - 1) / (RAW_X_MAX - RAW_X_MIN);
+        let screen_y = (raw_y as i32 - RAW_Y_MIN) * (PANEL_HEIGHT - 1) / (RAW_Y_MAX - RAW_Y_MIN);
+
+        let x = (PANEL_WIDTH - 1 - screen_x).clamp(0, PANEL_WIDTH - 1);
+        let y = screen_y.clamp(0, PANEL_HEIGHT - 1);
+        Some(Point::new(x, y))
+    }
 }